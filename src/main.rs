@@ -1,6 +1,8 @@
-use arrow2::array::{Array, Float64Array, Int32Array, StructArray};
+use arrow2::array::{Array, ListArray, MapArray, StructArray};
+use arrow2::datatypes::{DataType, Field as ArrowField};
 
 use libduckdb_sys::*;
+use polars::df;
 use polars::prelude::*;
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_void};
@@ -20,8 +22,8 @@ fn main() {
         }
 
         // Querying parquet files requires loading the parquet extension.
-        execute_statement(conn, "INSTALL parquet");
-        execute_statement(conn, "LOAD parquet");
+        execute_statement(conn, "INSTALL parquet").unwrap();
+        execute_statement(conn, "LOAD parquet").unwrap();
 
         let sql = "
         SELECT date_trunc('day', tpep_pickup_datetime) AS pickup_date, \
@@ -34,189 +36,183 @@ fn main() {
         FROM 'yellow_tripdata_2022-01.parquet' \
         GROUP BY 1";
 
+        // Streams the query's results batch-by-batch instead of fetching
+        // every Arrow chunk up front, so a large result set doesn't have to
+        // be materialized all at once.
+        let stream = ArrowDataFrameStream::query(conn, sql).unwrap();
+
+        for df in stream {
+            let out = df.unwrap().sum();
+            println!("{}", out);
+
+            // Round-trip each aggregated batch back into DuckDB. The first
+            // batch creates "daily_totals"; any further batches append to
+            // it instead of erroring out.
+            dataframe_to_duckdb(conn, "daily_totals", &out).unwrap();
+        }
+
+        // Demonstrate register_dataframe: expose an in-memory DataFrame as
+        // a queryable DuckDB table, with no copying, and read it back out
+        // through the same ArrowDataFrameStream path used above.
+        let city_populations = df!(
+            "city" => ["Austin", "Seattle"],
+            "population" => [961_855i64, 737_015i64],
+        )
+        .unwrap();
+
+        let _stream = register_dataframe(conn, "city_populations", &city_populations).unwrap();
+        let query = "SELECT * FROM city_populations ORDER BY city";
+        for df in ArrowDataFrameStream::query(conn, query).unwrap() {
+            println!("{}", df.unwrap());
+        }
+    }
+}
+
+/*
+ * Iterates over a DuckDB arrow query's results as Polars DataFrames, one
+ * Arrow batch at a time.
+ *
+ * DuckDB delivers results across multiple Arrow chunks, so we can't detect
+ * the end of the stream by comparing a running row count against
+ * duckdb_arrow_row_count -- that only reports the number of rows in the
+ * first chunk DuckDB handed back. Instead, each call to next() fetches a
+ * batch and treats a zero-length array as the end-of-stream signal.
+ *
+ * This replaces the manual fetch loop that used to live in main(), and
+ * moves the FFI cleanup (the "one more duckdb_query_arrow_array call, then
+ * duckdb_destroy_arrow" dance) into Drop so it runs even if a caller stops
+ * iterating early or a later computation panics.
+ */
+pub struct ArrowDataFrameStream {
+    result: duckdb_arrow,
+    done: bool,
+    config: ConversionConfig,
+}
+
+impl ArrowDataFrameStream {
+    /// Runs `sql` against `conn` via duckdb_query_arrow and returns an
+    /// iterator over its results, using the default `ConversionConfig`.
+    pub unsafe fn query(conn: duckdb_connection, sql: &str) -> Result<Self, String> {
+        Self::query_with_config(conn, sql, ConversionConfig::default())
+    }
+
+    /// Same as `query`, but lets the caller control lossy vs. exact handling
+    /// for types covered by `ConversionConfig` (currently just DECIMAL).
+    pub unsafe fn query_with_config(
+        conn: duckdb_connection,
+        sql: &str,
+        config: ConversionConfig,
+    ) -> Result<Self, String> {
         let sql = CString::new(sql).unwrap();
 
-        // This executes the query and prepares a data structure we use to fetch
-        // batches of results in Arrow arrays. `duckdb_arrow` is an alias for
-        // `void *` in DuckDB's C API. I don't know what is stored at this
-        // address once we execute `duckdb_query_arrow`, but we use it to
-        // consume results in the loop below.
         let mut result: duckdb_arrow = ptr::null_mut();
         let state = duckdb_query_arrow(conn, sql.as_ptr(), &mut result);
         if state == duckdb_state_DuckDBError {
             let error_message: *const c_char = duckdb_query_arrow_error(result);
-            let error_message = CStr::from_ptr(error_message).to_str().unwrap();
-            panic!("{}", error_message);
+            let error_message = CStr::from_ptr(error_message).to_str().unwrap().to_owned();
+            duckdb_destroy_arrow(&mut result);
+            return Err(error_message);
         }
 
-        // Time to consume the results of the query and do something with it
-        // using polars. Here we're going to:
-        //
-        // 1. Fetch a batch of results into an Arrow array. This is a C struct.
-        // 2. Convert that Arrow array into a safer and easier-to-use Rust arrow2::Array.
-        // 3. Construct a Polars dataframe from that arrow2::Array.
-        // 4. Do some computation over the batch of results.
+        Ok(ArrowDataFrameStream {
+            result,
+            done: false,
+            config,
+        })
+    }
+
+    /// Fetches and imports the next Arrow batch, or `None` once DuckDB signals
+    /// end-of-stream with a zero-length array.
+    unsafe fn next_batch(&mut self) -> Option<Box<dyn Array>> {
+        // arrow2::ffi::{ArrowArray, ArrowSchema} are representations of
+        // these structs:
         //
-        // We need to keep track of the result count so we break when all results
-        // have been consumed.
+        // https://arrow.apache.org/docs/format/CDataInterface.html#structure-definitions
+        let mut ffi_arrow_array: arrow2::ffi::ArrowArray = arrow2::ffi::ArrowArray::empty();
+        let state = duckdb_query_arrow_array(
+            self.result,
+            &mut &mut ffi_arrow_array as *mut _ as *mut *mut c_void,
+        );
+        if state != duckdb_state_DuckDBSuccess {
+            panic!("duckdb_query_arrow_array error");
+        }
 
-        let mut record_count = 0;
-        loop {
-            if record_count == duckdb_arrow_row_count(result).try_into().unwrap() {
-                break;
-            }
+        let mut schema = &arrow2::ffi::ArrowSchema::empty();
+        let schema = &mut schema;
+        let state = duckdb_query_arrow_schema(self.result, schema as *mut _ as *mut *mut c_void);
+        if state != duckdb_state_DuckDBSuccess {
+            panic!("duckdb_query_arrow_schema error");
+        }
 
-            ///////////////////////////////////////////////////////////////////
-            //               1. Fetch a batch of arrow results.              //
-            ///////////////////////////////////////////////////////////////////
+        let field = arrow2::ffi::import_field_from_c(schema).unwrap();
+        let arrow_array =
+            arrow2::ffi::import_array_from_c(ffi_arrow_array, field.data_type).expect("ok");
 
-            // arrow2::ffi::{ArrowArray, ArrowSchema} are representations of
-            // these structs:
-            //
-            // https://arrow.apache.org/docs/format/CDataInterface.html#structure-definitions
-            let mut ffi_arrow_array: arrow2::ffi::ArrowArray = arrow2::ffi::ArrowArray::empty();
-            let state = duckdb_query_arrow_array(
-                result,
-                &mut &mut ffi_arrow_array as *mut _ as *mut *mut c_void, // Help me understand this!! I got it from duckdb-rs.
-            );
+        if arrow_array.len() == 0 {
+            None
+        } else {
+            Some(arrow_array)
+        }
+    }
+}
 
-            if state != duckdb_state_DuckDBSuccess {
-                panic!("duckdb_query_arrow_array error");
-            }
+impl Iterator for ArrowDataFrameStream {
+    type Item = PolarsResult<DataFrame>;
 
-            let mut schema = &arrow2::ffi::ArrowSchema::empty();
-            let schema = &mut schema;
-            let state = duckdb_query_arrow_schema(result, schema as *mut _ as *mut *mut c_void);
-            if state != duckdb_state_DuckDBSuccess {
-                panic!("duckdb_query_arrow_schema error");
-            }
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
 
-            ///////////////////////////////////////////////////////////////////
-            //      2. Convert the C Arrow array into an arrow2::Array.      //
-            ///////////////////////////////////////////////////////////////////
+        let arrow_array = match unsafe { self.next_batch() } {
+            Some(arrow_array) => arrow_array,
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
 
-            let field = arrow2::ffi::import_field_from_c(schema).unwrap();
-            let arrow_array =
-                arrow2::ffi::import_array_from_c(ffi_arrow_array, field.data_type).expect("ok");
+        let struct_array = arrow_array
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .expect("This Arrow Array should be a StructArray.");
 
-            ///////////////////////////////////////////////////////////////////
-            //     3. Construct a polars dataframe from an arrow::Array.     //
-            ///////////////////////////////////////////////////////////////////
+        Some(struct_array_to_dataframe_with_config(
+            struct_array,
+            &self.config,
+        ))
+    }
+}
 
-            // We know our query is going to return a timestamp followed by 6
-            // floats. Each of these columns will be a series in our dataframe.
+impl Drop for ArrowDataFrameStream {
+    fn drop(&mut self) {
+        unsafe {
+            // DuckDB needs one more duckdb_query_arrow_array call to free the
+            // last batch it handed back -- but only if we haven't already
+            // made that call. next() treats the zero-length batch it gets
+            // back as the end-of-stream signal and sets `done`, so by the
+            // time we're fully drained we've already made this exact call;
+            // doing it again here would fetch past what DuckDB considers
+            // the end of the result.
             //
-            // DuckDB materializes its results in a StructArray:
-            // https://docs.rs/arrow2/latest/arrow2/array/struct.StructArray.html
-            //
-            // StructArrays just represent multiple arrays with the same number
-            // of rows. We need to take each array in the StructArray and turn
-            // it into a polars Series.
-
-            let struct_array = arrow_array
-                .as_any()
-                .downcast_ref::<StructArray>()
-                .expect("This Arrow Array should be a StructArray.");
-
-            /*
-             * If you want to find out what Arrow data types are being returned
-             * from your query it's helpful to print out the StructArray's fields.
-             *
-             * println!("{:?}", struct_array.fields());
-             *
-             * Each array in the StructArray will need to be downcast to the
-             * proper type.
-             *
-             * All Arrow datatypes are here:
-             * https://docs.rs/arrow2/latest/arrow2/datatypes/enum.DataType.html
-             *
-             * You can use all this info to do this dynamically. The example below
-             * is specific to the query we run above.
-             */
-
-            // A DataFrame is a vector of Series.
-            let mut df_series: Vec<Series> = vec![];
-
-            // The Arrow DataType for dates is Date32, which are are signed
-            // 32-bit integers.
-            let pickup_date_series = Series::try_from((
-                "pickup_date",
-                struct_array.values()[0]
-                    .as_any()
-                    .downcast_ref::<Int32Array>()
-                    .unwrap()
-                    .to_boxed(),
-            ))
-            .unwrap();
-
-            df_series.push(pickup_date_series);
-
-            let series_names = vec![
-                (1, "daily_passenger_count"),
-                (2, "daily_trip_distance"),
-                (3, "daily_tip_amount"),
-                (4, "daily_tolls_amount"),
-                (5, "daily_improvement_surcharge"),
-                (6, "daily_total"),
-            ];
-
-            for (idx, name) in series_names {
-                let series = Series::try_from((
-                    name,
-                    struct_array.values()[idx]
-                        .as_any()
-                        .downcast_ref::<Float64Array>()
-                        .unwrap()
-                        .to_boxed(),
-                ))
-                .unwrap();
-                df_series.push(series);
+            // See: https://duckdb.org/docs/api/c/api#duckdb_query_arrow_array
+            if !self.done {
+                let mut ffi_arrow_array: arrow2::ffi::ArrowArray = arrow2::ffi::ArrowArray::empty();
+                duckdb_query_arrow_array(
+                    self.result,
+                    &mut &mut ffi_arrow_array as *mut _ as *mut *mut c_void,
+                );
             }
 
-            let df = DataFrame::new(df_series).unwrap();
-
-            ///////////////////////////////////////////////////////////////////
-            //           4. Do some computation over the dataframe.          //
-            ///////////////////////////////////////////////////////////////////
-
-            let out = df.sum();
-            println!("{}", out);
-
-            record_count += arrow_array.len();
-        }
-
-        // I think we have actually have to call duckdb_query_arrow one more time.
-        // We don't care about the result -- it cleans up/frees the previous results
-        // it returns.
-        //
-        // See: https://duckdb.org/docs/api/c/api#duckdb_query_arrow_array
-        //
-        // The docs don't state this situation specifically, but if that call frees
-        // the previous `out_array`, then presumably we'd have a memory leak if
-        // we didn't do this.
-        //
-        // I might be wrong about this. This program doesn't crash, though -- I
-        // think that is a good sign.
-        let mut ffi_arrow_array: arrow2::ffi::ArrowArray = arrow2::ffi::ArrowArray::empty();
-        let state = duckdb_query_arrow_array(
-            result,
-            &mut &mut ffi_arrow_array as *mut _ as *mut *mut c_void, // Help me understand this!! I got it from duckdb-rs.
-        );
-        if state == duckdb_state_DuckDBError {
-            let error_message: *const c_char = duckdb_query_arrow_error(result);
-            let error_message = CStr::from_ptr(error_message).to_str().unwrap();
-            panic!("{}", error_message);
+            duckdb_destroy_arrow(&mut self.result);
         }
-
-        // Destroy the result struct. We're done with it.
-        duckdb_destroy_arrow(&mut result);
     }
 }
 
 /*
  * Executes a statement without fetching any results.
  */
-unsafe fn execute_statement(conn: duckdb_connection, statement: &str) {
+unsafe fn execute_statement(conn: duckdb_connection, statement: &str) -> Result<(), String> {
     let statement = CString::new(statement).unwrap();
 
     // DuckDB's C API has two query functions:
@@ -230,9 +226,277 @@ unsafe fn execute_statement(conn: duckdb_connection, statement: &str) {
 
     if state == duckdb_state_DuckDBError {
         let error_message: *const c_char = duckdb_result_error(&mut result);
-        let error_message = CStr::from_ptr(error_message).to_str().unwrap();
-        panic!("{}", error_message);
+        let error_message = CStr::from_ptr(error_message).to_str().unwrap().to_owned();
+        duckdb_destroy_result(&mut result);
+        return Err(error_message);
     }
 
     duckdb_destroy_result(&mut result);
+    Ok(())
+}
+
+/*
+ * Controls lossy vs. exact handling for Arrow types that don't map 1:1 onto
+ * a Polars Series.
+ *
+ * Right now that's just DECIMAL: DuckDB returns DECIMAL columns as Arrow
+ * Decimal128 (an i128 plus a scale), and Polars has no i128-backed numeric
+ * Series in this crate's dependency versions. The default, decimal_as_f64,
+ * divides each i128 by 10^scale and stores the result as f64 -- the same
+ * lossy-but-convenient tradeoff Polars itself makes when reading decimals.
+ * Set it to false to get an explicit error instead of silent precision loss.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct ConversionConfig {
+    pub decimal_as_f64: bool,
+}
+
+impl Default for ConversionConfig {
+    fn default() -> Self {
+        ConversionConfig {
+            decimal_as_f64: true,
+        }
+    }
+}
+
+/*
+ * Converts a DuckDB query result, delivered as an Arrow StructArray, into a
+ * Polars DataFrame. Each child array in the StructArray becomes a Series
+ * named after its field, so this works for any query's result shape rather
+ * than one hardcoded set of columns.
+ *
+ * Lets the caller control lossy vs. exact handling for types covered by
+ * ConversionConfig (currently just DECIMAL) -- pass &ConversionConfig::default()
+ * for the common case. ArrowDataFrameStream::query_with_config passes its
+ * caller's config through to this function for every batch.
+ */
+pub fn struct_array_to_dataframe_with_config(
+    struct_array: &StructArray,
+    config: &ConversionConfig,
+) -> PolarsResult<DataFrame> {
+    let series = struct_array
+        .fields()
+        .iter()
+        .zip(struct_array.values())
+        .map(|(field, array)| series_from_array(&field.name, array.as_ref(), config))
+        .collect::<PolarsResult<Vec<Series>>>()?;
+
+    DataFrame::new(series)
+}
+
+/*
+ * Converts a single Arrow array into a named Polars Series, dispatching on
+ * the array's DataType.
+ *
+ * Series::try_from already knows how to build a Series from an
+ * arrow2::array::Array once it's downcast to its concrete type, so this
+ * function's job is picking the right downcast for each DataType we expect
+ * DuckDB to hand back. Date32/Date64/Timestamp columns downcast to the same
+ * primitive arrays as their integer counterparts -- the logical type lives
+ * on the array itself and survives the to_boxed() call, so Series::try_from
+ * still produces a Date/Datetime Series rather than an Int32/Int64 one.
+ *
+ * List/LargeList columns are handled the same way: Series::try_from already
+ * knows how to turn an arrow2 ListArray into a Polars ListChunked Series, so
+ * we just downcast to the right offset width. Struct and Map columns need
+ * more than a downcast, since Polars represents them differently than arrow2
+ * does:
+ *
+ * - Struct: recurse through struct_array_to_dataframe_with_config to get a
+ *   Series per field, then fold those into a single struct Series. Recursing lets
+ *   arbitrarily deep/nested schemas work, not just one level of nesting.
+ * - Map: arrow2 represents a map as a ListArray whose values are a
+ *   StructArray of (key, value) entries. Polars doesn't have a distinct Map
+ *   type, so -- matching how Polars itself reads maps -- we rebuild that
+ *   same list-of-struct shape explicitly from the map's offsets and entries
+ *   field, then convert it like any other list column.
+ *
+ * Decimal columns go through `config` -- see ConversionConfig for why.
+ */
+fn series_from_array(
+    name: &str,
+    array: &dyn Array,
+    config: &ConversionConfig,
+) -> PolarsResult<Series> {
+    use arrow2::array::{
+        BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+        PrimitiveArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array, Utf8Array,
+    };
+
+    macro_rules! series {
+        ($ty:ty) => {
+            Series::try_from((name, array.as_any().downcast_ref::<$ty>().unwrap().to_boxed()))
+        };
+    }
+
+    match array.data_type() {
+        DataType::Int8 => series!(Int8Array),
+        DataType::Int16 => series!(Int16Array),
+        DataType::Int32 | DataType::Date32 => series!(Int32Array),
+        DataType::Int64 | DataType::Date64 | DataType::Timestamp(_, _) => series!(Int64Array),
+        DataType::UInt8 => series!(UInt8Array),
+        DataType::UInt16 => series!(UInt16Array),
+        DataType::UInt32 => series!(UInt32Array),
+        DataType::UInt64 => series!(UInt64Array),
+        DataType::Float32 => series!(Float32Array),
+        DataType::Float64 => series!(Float64Array),
+        DataType::Boolean => series!(BooleanArray),
+        DataType::Utf8 => series!(Utf8Array<i32>),
+        DataType::LargeUtf8 => series!(Utf8Array<i64>),
+        DataType::List(_) => series!(ListArray<i32>),
+        DataType::LargeList(_) => series!(ListArray<i64>),
+        DataType::Struct(_) => {
+            let nested = array.as_any().downcast_ref::<StructArray>().unwrap();
+            let nested_df = struct_array_to_dataframe_with_config(nested, config)?;
+            Ok(nested_df.into_struct(name).into_series())
+        }
+        DataType::Map(_, _) => {
+            let map_array = array.as_any().downcast_ref::<MapArray>().unwrap();
+            let entries = map_array.field();
+
+            let list_array = ListArray::<i32>::new(
+                DataType::List(Box::new(ArrowField::new(
+                    "entries",
+                    entries.data_type().clone(),
+                    true,
+                ))),
+                map_array.offsets().clone(),
+                entries.clone(),
+                map_array.validity().cloned(),
+            );
+
+            Series::try_from((name, list_array.to_boxed()))
+        }
+        DataType::Decimal(_, scale) => {
+            if !config.decimal_as_f64 {
+                return Err(PolarsError::ComputeError(
+                    format!(
+                        "column '{name}' is DECIMAL and ConversionConfig::decimal_as_f64 is \
+                         false, but exact decimal handling isn't implemented"
+                    )
+                    .into(),
+                ));
+            }
+
+            let decimal_array = array.as_any().downcast_ref::<PrimitiveArray<i128>>().unwrap();
+            let scale_factor = 10f64.powi(*scale as i32);
+
+            let floats: Float64Array = decimal_array
+                .iter()
+                .map(|value| value.map(|v| *v as f64 / scale_factor))
+                .collect();
+
+            Series::try_from((name, floats.to_boxed()))
+        }
+        DataType::Null => Ok(Series::new_null(name, array.len())),
+        other => Err(PolarsError::ComputeError(
+            format!("column '{name}' has unsupported Arrow DataType {other:?}").into(),
+        )),
+    }
+}
+
+/*
+ * Registers `df` as a named, queryable DuckDB table backed directly by its
+ * Arrow data, with no copying (except that a column split across several
+ * chunks is first rechunked into one): `name` can then be used anywhere a
+ * table name can, e.g. `SELECT * FROM name`, including joined against a
+ * parquet scan.
+ * This is the arrow_scan/replacement-scan pattern duckdb-rs calls an arrow
+ * vtab, done here at the raw C API level instead.
+ *
+ * duckdb_arrow_scan only borrows the Arrow data through the stream interface
+ * -- it doesn't take ownership of it -- so the returned ArrowArrayStream
+ * needs to stay alive for as long as `name` should remain queryable.
+ *
+ * IMPORTANT: the stream this builds is single-shot, backed by a one-element
+ * Rust iterator (via arrow2::ffi::export_iterator, which internally calls
+ * export_array_to_c per batch it hands out). Once DuckDB has scanned `name`
+ * once -- one `SELECT ... FROM name` -- the stream is exhausted. A second,
+ * independent scan against the same registration isn't an error, it just
+ * silently sees zero rows, because the Arrow C Stream protocol has no way to
+ * rewind a producer after it's signaled end-of-stream. If you need `name` to
+ * survive more than one scan, call register_dataframe again before each one.
+ */
+pub unsafe fn register_dataframe(
+    conn: duckdb_connection,
+    name: &str,
+    df: &DataFrame,
+) -> Result<Box<arrow2::ffi::ArrowArrayStream>, String> {
+    let struct_array = dataframe_to_struct_array(df)?;
+    let field = ArrowField::new(name, struct_array.data_type().clone(), false);
+
+    let batches: Vec<arrow2::error::Result<Box<dyn Array>>> = vec![Ok(struct_array.boxed())];
+    // Boxed so the address DuckDB keeps stays valid after we return it.
+    let mut stream = Box::new(arrow2::ffi::export_iterator(
+        Box::new(batches.into_iter()),
+        field,
+    ));
+
+    let name_c = CString::new(name).unwrap();
+    let state = duckdb_arrow_scan(
+        conn,
+        name_c.as_ptr(),
+        &mut *stream as *mut _ as duckdb_arrow_stream,
+    );
+    if state == duckdb_state_DuckDBError {
+        return Err(format!("duckdb_arrow_scan error registering '{name}'"));
+    }
+
+    Ok(stream)
+}
+
+/*
+ * Writes a Polars DataFrame into a DuckDB table -- the inverse of what
+ * struct_array_to_dataframe_with_config does. If `table_name` doesn't exist
+ * yet, it's created from `df`'s rows; if it already exists, `df`'s rows are
+ * appended to it instead, mirroring duckdb-rs's append_record_batch rather
+ * than erroring out on the second call for the same table.
+ *
+ * register_dataframe only registers a queryable scan under a name -- it
+ * doesn't write any data anywhere by itself -- so here we point SQL at that
+ * registration to actually materialize the rows into `table_name`.
+ */
+pub unsafe fn dataframe_to_duckdb(
+    conn: duckdb_connection,
+    table_name: &str,
+    df: &DataFrame,
+) -> Result<(), String> {
+    let scan_name = format!("{table_name}_arrow_export");
+    let _stream = register_dataframe(conn, &scan_name, df)?;
+
+    let create_table_sql = format!("CREATE TABLE \"{table_name}\" AS SELECT * FROM \"{scan_name}\"");
+    if let Err(create_error) = execute_statement(conn, &create_table_sql) {
+        // Only treat this as "the table already exists, append instead" if
+        // the error actually says so -- anything else (a bad column type
+        // from the exported Arrow schema, permissions, disk full, ...) is a
+        // real failure, and swallowing it in favor of the INSERT's
+        // (probably unrelated) error would be actively misleading.
+        if !create_error.to_lowercase().contains("already exists") {
+            return Err(create_error);
+        }
+
+        let insert_sql = format!("INSERT INTO \"{table_name}\" SELECT * FROM \"{scan_name}\"");
+        execute_statement(conn, &insert_sql)?;
+    }
+
+    Ok(())
+}
+
+/*
+ * Bundles a DataFrame's Series back into a single Arrow StructArray -- the
+ * inverse of the fields().zip(values()) walk struct_array_to_dataframe_with_config
+ * does. This is the row-batch shape DuckDB's Arrow interface expects.
+ */
+fn dataframe_to_struct_array(df: &DataFrame) -> Result<StructArray, String> {
+    let mut fields = Vec::with_capacity(df.width());
+    let mut values: Vec<Box<dyn Array>> = Vec::with_capacity(df.width());
+
+    for series in df.iter() {
+        let series = series.rechunk();
+        let array = series.chunks()[0].clone();
+        fields.push(ArrowField::new(series.name(), array.data_type().clone(), true));
+        values.push(array);
+    }
+
+    Ok(StructArray::new(DataType::Struct(fields), values, None))
 }